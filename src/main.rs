@@ -1,10 +1,11 @@
 use std::{
     fs::{self, File},
-    io::{self, BufRead, BufReader, Write},
-    path::PathBuf,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
 };
 use regex::Regex;
 use clap::Parser;
+use flate2::read::ZlibDecoder;
 
 /// Splits a unified diff from standard input into individual files in a target directory.
 #[derive(Parser, Debug)]
@@ -24,13 +25,43 @@ struct Args {
     /// Flag to skip the diff header
     #[arg(long)]
     skip_header: bool,
+
+    /// Print the modified line ranges of each file's new image instead of writing split diffs
+    #[arg(long)]
+    ranges: bool,
+
+    /// Only split out files whose resolved path matches this regex
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Invert `--filter` so everything except matching files is split out
+    #[arg(long)]
+    invert_filter: bool,
+
+    /// Emit a JSON summary of the whole patch instead of writing files
+    #[arg(long)]
+    json: bool,
+
+    /// Decode `GIT binary patch` blocks and materialize literal binary files
+    #[arg(long)]
+    decode_binary: bool,
 }
 
 fn main() -> io::Result<()> {
     let args = Args::parse();
 
+    if args.invert_filter && args.filter.is_none() {
+        eprintln!("Error: --invert-filter requires --filter !!!!");
+        std::process::exit(1);
+    }
+
+    // The streaming modes write nothing to the target directory.
+    let writes_files = !args.json && !args.ranges;
+
     // Create the target directory if it doesn't already exist.
-    fs::create_dir_all(&args.target_path)?;
+    if writes_files {
+        fs::create_dir_all(&args.target_path)?;
+    }
 
     // --- In-Memory Diff Processing ---
 
@@ -39,14 +70,17 @@ fn main() -> io::Result<()> {
 
     let mut binary_file_lines: Vec<String> = Vec::new();
     let mut current_file_lines: Vec<String> = Vec::new();
-    let mut full_path: Option<PathBuf> = None;
-    let mut is_binary = false;
+    let mut summaries: Vec<FileSummary> = Vec::new();
+    let mut unresolved_binaries: Vec<String> = Vec::new();
 
     // Regex for generalizing @@ lines
     let re = Regex::new(r"(@@ -[0-9]+)(,[0-9]+)?( \+[0-9]+)(,[0-9]+)?( @@)").unwrap();
     // Regex for @@@ lines for "--cc" and "--combine"
     let re_combine = Regex::new(r"(@@@ -[0-9]+)(,[0-9]+)?( \-[0-9]+)(,[0-9]+)?( \+[0-9]+)(,[0-9]+)?( @@@)").unwrap();
 
+    // File-selection filter, compiled once.
+    let filter = args.filter.as_ref().map(|pattern| Regex::new(pattern).unwrap());
+
     let mut buffer = Vec::new();
     let mut header_state = HeaderState::Diff;
 
@@ -56,66 +90,35 @@ fn main() -> io::Result<()> {
         match header_state {
             HeaderState::Diff => {
                 if line.starts_with("diff --") {
-                    if !current_file_lines.is_empty() && full_path.is_some() && !is_binary {
-                        process_file_diff(&current_file_lines, full_path.as_ref().unwrap(), &args, &re, &re_combine)?;
-                    }
+                    flush_file(&current_file_lines, &args, &re, &re_combine, filter.as_ref(), &mut summaries, &mut unresolved_binaries)?;
                     current_file_lines.clear();
-                    full_path = None;
-                    is_binary = false;
                     current_file_lines.push(line.clone());
-                    header_state = HeaderState::FromOrIndex;
+                    header_state = HeaderState::Header;
                 }
             }
-            HeaderState::FromOrIndex => {
-                if line.starts_with("index ") {
-                    current_file_lines.push(line.clone());
-                    header_state = HeaderState::From;
-                } else if line.starts_with("--- ") {
+            HeaderState::Header => {
+                if line.starts_with("diff --") {
+                    // A new file begins before any body: flush the (possibly
+                    // zero-hunk) rename/mode-only entry we just collected.
+                    flush_file(&current_file_lines, &args, &re, &re_combine, filter.as_ref(), &mut summaries, &mut unresolved_binaries)?;
+                    current_file_lines.clear();
                     current_file_lines.push(line.clone());
-                    header_state = HeaderState::To;
-                } else {
-                    eprintln!("Error: Invalid diff format. Expected 'index ' or '--- ' line !!!!");
-                    std::process::exit(1);
-                }
-            }
-            HeaderState::From => {
-                if line.starts_with("--- ") {
+                } else if is_extended_header(&line) {
                     current_file_lines.push(line.clone());
-                    header_state = HeaderState::To;
                 } else {
-                    eprintln!("Error: Invalid diff format. Expected '--- ' line !!!!");
-                    std::process::exit(1);
-                }
-            }
-            HeaderState::To => {
-                if line.starts_with("+++ ") {
-                    let path_str = extract_path(&line, "+++ ");
-                    if !path_str.is_empty() {
-                        full_path = Some(PathBuf::from(path_str));
-                    }
-                    current_file_lines.push(line.clone());
+                    // First non-header line: fall through to body handling.
                     header_state = HeaderState::Body;
-                } else {
-                    eprintln!("Error: Invalid diff format. Expected '+++ ' line !!!!");
-                    std::process::exit(1);
+                    handle_body_line(&line, &mut current_file_lines, &mut binary_file_lines, filter.as_ref(), &args);
                 }
             }
             HeaderState::Body => {
                 if line.starts_with("diff --") {
-                    if !current_file_lines.is_empty() && full_path.is_some() && !is_binary {
-                        process_file_diff(&current_file_lines, full_path.as_ref().unwrap(), &args, &re, &re_combine)?;
-                    }
+                    flush_file(&current_file_lines, &args, &re, &re_combine, filter.as_ref(), &mut summaries, &mut unresolved_binaries)?;
                     current_file_lines.clear();
-                    full_path = None;
-                    is_binary = false;
                     current_file_lines.push(line.clone());
-                    header_state = HeaderState::FromOrIndex;
+                    header_state = HeaderState::Header;
                 } else {
-                    if line.starts_with("Binary files ") {
-                        is_binary = true;
-                        binary_file_lines.push(line.trim_end().to_string());
-                    }
-                    current_file_lines.push(line.clone());
+                    handle_body_line(&line, &mut current_file_lines, &mut binary_file_lines, filter.as_ref(), &args);
                 }
             }
         }
@@ -124,8 +127,16 @@ fn main() -> io::Result<()> {
     }
 
     // Process the last file's diff
-    if !current_file_lines.is_empty() && full_path.is_some() && !is_binary {
-        process_file_diff(&current_file_lines, full_path.as_ref().unwrap(), &args, &re, &re_combine)?;
+    flush_file(&current_file_lines, &args, &re, &re_combine, filter.as_ref(), &mut summaries, &mut unresolved_binaries)?;
+
+    if args.json {
+        println!("{}", summaries_to_json(&summaries));
+        return Ok(());
+    }
+
+    // `--ranges` already streamed its map to stdout; keep stdout clean.
+    if args.ranges {
+        return Ok(());
     }
 
     if !binary_file_lines.is_empty() {
@@ -136,6 +147,14 @@ fn main() -> io::Result<()> {
         }
     }
 
+    if !unresolved_binaries.is_empty() {
+        let unresolved_path = args.target_path.join("__UNRESOLVED_BINARY__.txt");
+        let mut unresolved_file = File::create(&unresolved_path)?;
+        for line in &unresolved_binaries {
+            writeln!(unresolved_file, "{}", line)?;
+        }
+    }
+
     println!("Processing complete. Files created in '{}'.", args.target_path.display());
 
     Ok(())
@@ -143,9 +162,7 @@ fn main() -> io::Result<()> {
 
 enum HeaderState {
     Diff,
-    FromOrIndex,
-    From,
-    To,
+    Header,
     Body,
 }
 
@@ -154,13 +171,460 @@ fn extract_path<'a>(line: &'a str, prefix: &str) -> &'a str {
     line.split('\t').next().unwrap_or(line)
 }
 
-fn process_file_diff(
+/// Extended-header lines git emits between `diff --git` and the hunks.
+///
+/// These describe renames, copies, mode changes and new/deleted files; we keep
+/// them verbatim so the reconstructed per-file output stays faithful, and so
+/// rename/mode-only entries (which carry no `---`/`+++`) still resolve a path.
+fn is_extended_header(line: &str) -> bool {
+    const PREFIXES: [&str; 13] = [
+        "index ",
+        "old mode ",
+        "new mode ",
+        "new file mode ",
+        "deleted file mode ",
+        "similarity index ",
+        "dissimilarity index ",
+        "rename from ",
+        "rename to ",
+        "copy from ",
+        "copy to ",
+        "--- ",
+        "+++ ",
+    ];
+    PREFIXES.iter().any(|prefix| line.starts_with(prefix))
+}
+
+/// Record a body line, flagging (and optionally logging) binary-file markers.
+fn handle_body_line(
+    line: &str,
+    current_file_lines: &mut Vec<String>,
+    binary_file_lines: &mut Vec<String>,
+    filter: Option<&Regex>,
+    args: &Args,
+) {
+    if line.starts_with("Binary files ") {
+        let path = derive_path(current_file_lines)
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|| line.trim_end().to_string());
+        if passes_filter(&path, filter, args.invert_filter) {
+            binary_file_lines.push(line.trim_end().to_string());
+        }
+    }
+    current_file_lines.push(line.to_string());
+}
+
+/// Emit a completed file entry, deriving its output path from the headers.
+///
+/// In `--json` mode a [`FileSummary`] is collected into `summaries` (binary
+/// entries included); otherwise the file is dispatched to the active mode.
+fn flush_file(
+    lines: &[String],
+    args: &Args,
+    re: &Regex,
+    re_combine: &Regex,
+    filter: Option<&Regex>,
+    summaries: &mut Vec<FileSummary>,
+    unresolved_binaries: &mut Vec<String>,
+) -> io::Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let is_binary = lines.iter().any(|line| line.starts_with("Binary files "));
+    if args.json {
+        if let Some(summary) = summarize_file(lines, re, re_combine) {
+            let path = summary.new_path.clone().unwrap_or_default();
+            if passes_filter(&path, filter, args.invert_filter) {
+                summaries.push(summary);
+            }
+        }
+        return Ok(());
+    }
+    if args.decode_binary && !args.ranges && decode_binary_patch(lines, args, filter, unresolved_binaries)? {
+        return Ok(());
+    }
+    if is_binary {
+        return Ok(());
+    }
+    if let Some(full_path) = derive_path(lines) {
+        process_file(lines, &full_path, args, re, re_combine, filter)?;
+    }
+    Ok(())
+}
+
+/// Work out the new-image path of a file section, tolerating diffs that have no
+/// `+++` line (pure renames, copies and mode changes).
+fn derive_path(lines: &[String]) -> Option<PathBuf> {
+    // Prefer the `+++` path when it names a real file.
+    if let Some(path) = lines
+        .iter()
+        .find(|line| line.starts_with("+++ "))
+        .map(|line| extract_path(line, "+++ "))
+    {
+        if !path.is_empty() && path != "/dev/null" {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    // Otherwise fall back to the rename/copy destination.
+    for prefix in ["rename to ", "copy to "] {
+        if let Some(line) = lines.iter().find(|line| line.starts_with(prefix)) {
+            let path = line.trim_start_matches(prefix).trim();
+            if !path.is_empty() {
+                return Some(PathBuf::from(path));
+            }
+        }
+    }
+
+    // Last resort: the `b/…` side of the `diff --git` line.
+    lines
+        .first()
+        .and_then(|line| path_from_diff_git(line))
+        .map(PathBuf::from)
+}
+
+/// Extract the `b/…` path from a `diff --git a/… b/…` line.
+fn path_from_diff_git(line: &str) -> Option<String> {
+    paths_from_diff_git(line).map(|(_, b)| b)
+}
+
+/// Split a `diff --git a/… b/…` line into its `a/…` and `b/…` paths.
+fn paths_from_diff_git(line: &str) -> Option<(String, String)> {
+    let rest = line.trim_end().strip_prefix("diff --git ")?;
+    let idx = rest.find(" b/")?;
+    Some((rest[..idx].to_string(), rest[idx + 1..].to_string()))
+}
+
+/// A single hunk's `-a,b +c,d` coordinates.
+struct HunkCoords {
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+}
+
+/// Machine-readable summary of one file's changes, produced by `--json`.
+struct FileSummary {
+    old_path: Option<String>,
+    new_path: Option<String>,
+    strip: usize,
+    binary: bool,
+    added: usize,
+    removed: usize,
+    context: usize,
+    hunks: Vec<HunkCoords>,
+}
+
+/// Build a [`FileSummary`] for a file section by scanning its headers and body.
+fn summarize_file(lines: &[String], re: &Regex, re_combine: &Regex) -> Option<FileSummary> {
+    let from_path = lines
+        .iter()
+        .find(|line| line.starts_with("--- "))
+        .map(|line| extract_path(line, "--- "))
+        .unwrap_or("");
+    let to_path = lines
+        .iter()
+        .find(|line| line.starts_with("+++ "))
+        .map(|line| extract_path(line, "+++ "))
+        .unwrap_or("");
+
+    let new_path = derive_path(lines).map(|path| path.to_string_lossy().into_owned());
+    let old_path = if from_path.is_empty() || from_path == "/dev/null" {
+        None
+    } else {
+        Some(from_path.to_string())
+    };
+    if old_path.is_none() && new_path.is_none() {
+        return None;
+    }
+
+    // Fall back to the `diff --git a/… b/…` paths when `---`/`+++` are absent,
+    // so the reported strip matches where `resolve_stripped_path` would write.
+    let mut strip_from = from_path.to_string();
+    let mut strip_to = to_path.to_string();
+    if strip_from.is_empty() || strip_to.is_empty() {
+        if let Some((a, b)) = lines.first().and_then(|line| paths_from_diff_git(line)) {
+            if strip_from.is_empty() {
+                strip_from = a;
+            }
+            if strip_to.is_empty() {
+                strip_to = b;
+            }
+        }
+    }
+    let strip = calculate_strip_value(&strip_from, &strip_to);
+
+    let mut summary = FileSummary {
+        old_path,
+        new_path,
+        strip,
+        binary: false,
+        added: 0,
+        removed: 0,
+        context: 0,
+        hunks: Vec::new(),
+    };
+
+    let mut in_hunk = false;
+    for line in lines {
+        let trimmed_line = line.trim_end();
+
+        if trimmed_line.starts_with("Binary files ") || trimmed_line == "GIT binary patch" {
+            summary.binary = true;
+            break;
+        }
+
+        if trimmed_line.starts_with("@@@ ") {
+            if let Some(caps) = re_combine.captures(trimmed_line) {
+                summary.hunks.push(HunkCoords {
+                    old_start: hunk_number(caps.get(1)),
+                    old_lines: hunk_number(caps.get(2)),
+                    new_start: hunk_number(caps.get(5)),
+                    new_lines: hunk_number(caps.get(6)),
+                });
+                in_hunk = true;
+            }
+            continue;
+        } else if trimmed_line.starts_with("@@ ") {
+            if let Some(caps) = re.captures(trimmed_line) {
+                summary.hunks.push(HunkCoords {
+                    old_start: hunk_number(caps.get(1)),
+                    old_lines: hunk_number(caps.get(2)),
+                    new_start: hunk_number(caps.get(3)),
+                    new_lines: hunk_number(caps.get(4)),
+                });
+                in_hunk = true;
+            }
+            continue;
+        }
+
+        if !in_hunk {
+            continue;
+        }
+
+        match line.chars().next() {
+            // `\ No newline at end of file` markers aren't real body lines.
+            Some('\\') => continue,
+            Some('+') => summary.added += 1,
+            Some('-') => summary.removed += 1,
+            _ => summary.context += 1,
+        }
+    }
+
+    Some(summary)
+}
+
+/// Parse the integer out of a hunk-coordinate capture group, defaulting the
+/// line-count groups (`,b`/`,d`) to `1` when absent, as git does.
+fn hunk_number(group: Option<regex::Match>) -> usize {
+    group
+        .and_then(|m| {
+            m.as_str()
+                .trim_start_matches([' ', '+', '-', ',', '@'])
+                .parse()
+                .ok()
+        })
+        .unwrap_or(1)
+}
+
+/// Serialize the collected file summaries as a JSON array.
+fn summaries_to_json(summaries: &[FileSummary]) -> String {
+    let mut out = String::from("[");
+    for (i, file) in summaries.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"old_path\":");
+        out.push_str(&json_opt_string(file.old_path.as_deref()));
+        out.push_str(",\"new_path\":");
+        out.push_str(&json_opt_string(file.new_path.as_deref()));
+        out.push_str(&format!(
+            ",\"strip\":{},\"binary\":{},\"added\":{},\"removed\":{},\"context\":{},\"hunks\":[",
+            file.strip, file.binary, file.added, file.removed, file.context
+        ));
+        for (j, hunk) in file.hunks.iter().enumerate() {
+            if j > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!(
+                "{{\"old_start\":{},\"old_lines\":{},\"new_start\":{},\"new_lines\":{}}}",
+                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+            ));
+        }
+        out.push_str("]}");
+    }
+    out.push(']');
+    out
+}
+
+/// Render an optional string as a JSON string literal or `null`.
+fn json_opt_string(value: Option<&str>) -> String {
+    match value {
+        Some(s) => format!("\"{}\"", json_escape(s)),
+        None => "null".to_string(),
+    }
+}
+
+/// Escape the characters JSON strings require escaping.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// git's base85 alphabet (see `base85.c` in git), 85 symbols long.
+const BASE85: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Decode a `GIT binary patch` block into a file on disk.
+///
+/// The block starts with a `literal <size>`/`delta <size>` header followed by
+/// length-prefixed base85 lines of a zlib-deflated payload. `literal` payloads
+/// are inflated and written into the target directory; `delta` payloads need the
+/// base blob to apply, so their paths are recorded in `unresolved_binaries`
+/// instead. Returns `true` when a binary patch was recognized and handled.
+fn decode_binary_patch(
     lines: &[String],
-    full_path_buf: &PathBuf,
+    args: &Args,
+    filter: Option<&Regex>,
+    unresolved_binaries: &mut Vec<String>,
+) -> io::Result<bool> {
+    let start = match lines.iter().position(|line| line.trim_end() == "GIT binary patch") {
+        Some(idx) => idx,
+        None => return Ok(false),
+    };
+
+    let full_path = match derive_path(lines) {
+        Some(path) => path,
+        None => return Ok(true),
+    };
+    if !passes_filter(&full_path.to_string_lossy(), filter, args.invert_filter) {
+        return Ok(true);
+    }
+    let stripped_path = match resolve_stripped_path(lines, &full_path, args) {
+        Some(path) => path,
+        None => return Ok(true),
+    };
+
+    let block = &lines[start + 1..];
+    let header = match block.first() {
+        Some(line) => line.trim(),
+        None => return Ok(true),
+    };
+    let mut fields = header.split_whitespace();
+    let kind = fields.next().unwrap_or("");
+    let size: usize = fields.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+
+    if kind == "delta" {
+        // Delta patches require the pre-image blob we don't have here.
+        unresolved_binaries.push(stripped_path.to_string_lossy().into_owned());
+        return Ok(true);
+    }
+    if kind != "literal" {
+        return Ok(true);
+    }
+
+    let mut deflated = Vec::new();
+    for line in &block[1..] {
+        let trimmed_line = line.trim_end();
+        if trimmed_line.is_empty() {
+            break; // Blank line terminates the forward-patch block.
+        }
+        match decode_base85_line(trimmed_line) {
+            Some(mut bytes) => deflated.append(&mut bytes),
+            None => break,
+        }
+    }
+
+    let mut decoder = ZlibDecoder::new(&deflated[..]);
+    let mut data = Vec::new();
+    decoder.read_to_end(&mut data)?;
+    if size != 0 && data.len() > size {
+        data.truncate(size);
+    }
+
+    let output_file = args.target_path.join(&stripped_path);
+    if let Some(parent) = output_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&output_file, &data)?;
+
+    Ok(true)
+}
+
+/// Decode one length-prefixed git-base85 line into its raw bytes.
+fn decode_base85_line(line: &str) -> Option<Vec<u8>> {
+    let bytes = line.as_bytes();
+    let count = match bytes.first()? {
+        c @ b'A'..=b'Z' => (c - b'A' + 1) as usize,
+        c @ b'a'..=b'z' => (c - b'a' + 27) as usize,
+        _ => return None,
+    };
+
+    let payload = &bytes[1..];
+    let mut out = Vec::with_capacity(count);
+    let mut pos = 0;
+    while out.len() < count {
+        let mut acc: u64 = 0;
+        for _ in 0..5 {
+            let symbol = *payload.get(pos)?;
+            let digit = BASE85.iter().position(|&candidate| candidate == symbol)?;
+            acc = acc * 85 + digit as u64;
+            pos += 1;
+        }
+        let word = acc as u32;
+        for shift in [24, 16, 8, 0] {
+            if out.len() < count {
+                out.push((word >> shift) as u8);
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Decide whether a path should be split out given the active `--filter`.
+///
+/// With no filter every path is kept; otherwise a match is kept unless
+/// `--invert-filter` flips the sense.
+fn passes_filter(path: &str, filter: Option<&Regex>, invert: bool) -> bool {
+    match filter {
+        Some(re) => re.is_match(path) ^ invert,
+        None => true,
+    }
+}
+
+/// Dispatch a completed file diff to the mode selected on the command line.
+fn process_file(
+    lines: &[String],
+    full_path_buf: &Path,
     args: &Args,
     re: &Regex,
     re_combine: &Regex,
+    filter: Option<&Regex>,
 ) -> io::Result<()> {
+    if !passes_filter(&full_path_buf.to_string_lossy(), filter, args.invert_filter) {
+        return Ok(());
+    }
+    if args.ranges {
+        process_file_ranges(lines, full_path_buf, args, re, re_combine)
+    } else {
+        process_file_diff(lines, full_path_buf, args, re, re_combine)
+    }
+}
+
+/// Resolve the `--strip`-adjusted output path for a file diff, returning `None`
+/// when the path is empty after stripping (and should therefore be skipped).
+fn resolve_stripped_path(lines: &[String], full_path_buf: &Path, args: &Args) -> Option<PathBuf> {
     let from_path_str = lines
         .iter()
         .find(|line| line.starts_with("--- "))
@@ -173,9 +637,23 @@ fn process_file_diff(
         .map(|line| extract_path(line, "+++ "))
         .unwrap_or("");
 
+    // Diffs without `---`/`+++` (binary patches, pure renames) still carry the
+    // `a/…` and `b/…` paths on the `diff --git` line; fall back to those.
+    let mut from_path = from_path_str.to_string();
+    let mut to_path = to_path_str.to_string();
+    if from_path.is_empty() || to_path.is_empty() {
+        if let Some((a, b)) = lines.first().and_then(|line| paths_from_diff_git(line)) {
+            if from_path.is_empty() {
+                from_path = a;
+            }
+            if to_path.is_empty() {
+                to_path = b;
+            }
+        }
+    }
 
     let strip_value = if args.strip == -1 {
-        calculate_strip_value(from_path_str, to_path_str)
+        calculate_strip_value(&from_path, &to_path)
     } else {
         args.strip as usize
     };
@@ -188,16 +666,121 @@ fn process_file_diff(
         } else {
             full_path_buf.file_name().map_or_else(
                 || PathBuf::from(""),
-                |os_str| PathBuf::from(os_str),
+                PathBuf::from,
             )
         }
     } else {
-        full_path_buf.clone()
+        full_path_buf.to_path_buf()
     };
 
     if stripped_path.as_os_str().is_empty() {
-        return Ok(()); // Skip if the path is empty after stripping
+        None // Skip if the path is empty after stripping
+    } else {
+        Some(stripped_path)
     }
+}
+
+/// Walk a file's hunks and print the modified line ranges of its new image.
+///
+/// For every hunk header the new-file start column is taken (the trailing `+c,d`
+/// group for combined `@@@` headers), then the body is scanned keeping a running
+/// new-line counter that advances on context and added lines but not removed
+/// ones; every added line's number is recorded and consecutive numbers are
+/// coalesced into inclusive `[start,end]` ranges.
+fn process_file_ranges(
+    lines: &[String],
+    full_path_buf: &Path,
+    args: &Args,
+    re: &Regex,
+    re_combine: &Regex,
+) -> io::Result<()> {
+    let stripped_path = match resolve_stripped_path(lines, full_path_buf, args) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let mut changed: Vec<usize> = Vec::new();
+    let mut new_line = 0usize;
+    let mut in_hunk = false;
+    // Combined `@@@` hunks carry one prefix column per parent.
+    let mut prefix_len = 1;
+
+    for line in lines {
+        let trimmed_line = line.trim_end();
+
+        if trimmed_line.starts_with("@@@ ") {
+            if let Some(caps) = re_combine.captures(trimmed_line) {
+                new_line = parse_hunk_start(caps.get(5));
+                in_hunk = true;
+                prefix_len = 2;
+            }
+            continue;
+        } else if trimmed_line.starts_with("@@ ") {
+            if let Some(caps) = re.captures(trimmed_line) {
+                new_line = parse_hunk_start(caps.get(3));
+                in_hunk = true;
+                prefix_len = 1;
+            }
+            continue;
+        }
+
+        if !in_hunk {
+            continue;
+        }
+
+        let prefix = line.get(..prefix_len).unwrap_or(line);
+        if prefix.starts_with('\\') {
+            // `\ No newline at end of file` markers annotate the previous line
+            // and don't advance the new-file counter.
+            continue;
+        } else if prefix.contains('-') {
+            // Removed from a parent: not part of the new image.
+        } else if prefix.contains('+') {
+            changed.push(new_line);
+            new_line += 1;
+        } else {
+            new_line += 1;
+        }
+    }
+
+    // Coalesce consecutive line numbers into inclusive ranges.
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &num in &changed {
+        match ranges.last_mut() {
+            Some(last) if num == last.1 + 1 => last.1 = num,
+            _ => ranges.push((num, num)),
+        }
+    }
+
+    let ranges_str = ranges
+        .iter()
+        .map(|(start, end)| format!("{},{}", start, end))
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!("{}: {}", stripped_path.display(), ranges_str);
+
+    Ok(())
+}
+
+/// Parse the starting new-file line number from a `( \+N)` capture group.
+fn parse_hunk_start(group: Option<regex::Match>) -> usize {
+    group
+        .map(|m| m.as_str().trim_start_matches([' ', '+']))
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(1)
+}
+
+fn process_file_diff(
+    lines: &[String],
+    full_path_buf: &Path,
+    args: &Args,
+    re: &Regex,
+    re_combine: &Regex,
+) -> io::Result<()> {
+    let stripped_path = match resolve_stripped_path(lines, full_path_buf, args) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
 
     // Ensure the parent directory for the output file exists
     let output_file = args.target_path.join(&stripped_path);